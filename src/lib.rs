@@ -1,12 +1,44 @@
+mod request;
+mod response;
+mod router;
+mod static_files;
+
+pub use request::Request;
+pub use response::Response;
+pub use router::Router;
+
 use std::error::Error;
 use std::fmt;
 use std::fs;
-use std::io::{BufRead, BufReader, Write};
+use std::io::BufReader;
 use std::net::{TcpListener, TcpStream};
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+const HELLO_HTML: &str = "<!DOCTYPE html>
+<html>
+<head>
+    <title>Hello</title>
+</head>
+<body>
+<h1>Hello, world!</h1>
+<p>Welcome to ThreadedHTTP server</p>
+</body>
+</html>";
+
+const NOT_FOUND_HTML: &str = "<!DOCTYPE html>
+<html>
+<head>
+    <title>404</title>
+</head>
+<body>
+<h1>404 Not Found</h1>
+<p>The requested resource was not found.</p>
+</body>
+</html>";
 
 type Job = Box<dyn FnOnce() + Send + 'static>;
 
@@ -91,17 +123,46 @@ impl Drop for ThreadPool {
     }
 }
 
+/// What kind of failure a `ServerError` represents, so callers can map it
+/// to an appropriate HTTP status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServerErrorKind {
+    BadRequest,
+    Timeout,
+    Internal,
+}
+
 #[derive(Debug)]
 pub struct ServerError {
+    kind: ServerErrorKind,
     message: String,
 }
 
 impl ServerError {
     pub fn new(msg: &str) -> ServerError {
         ServerError {
+            kind: ServerErrorKind::BadRequest,
             message: msg.to_string(),
         }
     }
+
+    pub fn timeout(msg: &str) -> ServerError {
+        ServerError {
+            kind: ServerErrorKind::Timeout,
+            message: msg.to_string(),
+        }
+    }
+
+    pub fn internal(msg: &str) -> ServerError {
+        ServerError {
+            kind: ServerErrorKind::Internal,
+            message: msg.to_string(),
+        }
+    }
+
+    pub fn kind(&self) -> ServerErrorKind {
+        self.kind
+    }
 }
 
 impl fmt::Display for ServerError {
@@ -112,10 +173,24 @@ impl fmt::Display for ServerError {
 
 impl Error for ServerError {}
 
+#[derive(Clone)]
 pub struct ServerConfig {
     pub address: String,
     pub pool_size: usize,
     pub verbose: bool,
+    pub document_root: PathBuf,
+    /// Timeout applied to each read from a connected client.
+    pub read_timeout: Duration,
+    /// Timeout applied to each write to a connected client.
+    pub write_timeout: Duration,
+    /// Maximum number of connections handled concurrently. Once reached,
+    /// the accept loop stops pulling new connections off the socket until
+    /// a slot frees up.
+    pub max_connections: usize,
+    /// How long to wait for in-flight connections to finish during
+    /// shutdown before the `ThreadPool` is dropped and workers are joined
+    /// regardless.
+    pub shutdown_timeout: Duration,
 }
 
 impl Default for ServerConfig {
@@ -124,13 +199,29 @@ impl Default for ServerConfig {
             address: "127.0.0.1:7878".to_string(),
             pool_size: 4,
             verbose: false,
+            document_root: PathBuf::from("public"),
+            read_timeout: Duration::from_secs(10),
+            write_timeout: Duration::from_secs(10),
+            max_connections: 100,
+            shutdown_timeout: Duration::from_secs(30),
         }
     }
 }
 
+/// Decrements the shared active-connection counter when a connection's
+/// handler finishes, including on panic.
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
 pub fn start_server(
     config: ServerConfig,
     shutdown: Arc<AtomicBool>,
+    router: Router,
 ) -> Result<(), Box<dyn Error>> {
     let listener = TcpListener::bind(&config.address)?;
     listener.set_nonblocking(true)?;
@@ -140,8 +231,11 @@ pub fn start_server(
     }
 
     let pool = ThreadPool::new(config.pool_size);
+    let router = Arc::new(router);
+    let active_connections = Arc::new(AtomicUsize::new(0));
+    let config = Arc::new(config);
 
-    for stream in listener.incoming() {
+    loop {
         if shutdown.load(Ordering::Relaxed) {
             if config.verbose {
                 println!("Shutdown signal received, stopping server...");
@@ -149,11 +243,28 @@ pub fn start_server(
             break;
         }
 
-        match stream {
-            Ok(stream) => {
-                let verbose = config.verbose;
+        if active_connections.load(Ordering::SeqCst) >= config.max_connections {
+            thread::sleep(Duration::from_millis(10));
+            continue;
+        }
+
+        match listener.accept() {
+            Ok((stream, _addr)) => {
+                let config = Arc::clone(&config);
+                let router = Arc::clone(&router);
+                let active_connections = Arc::clone(&active_connections);
+                active_connections.fetch_add(1, Ordering::SeqCst);
+
+                if config.verbose {
+                    println!(
+                        "Active connections: {}",
+                        active_connections.load(Ordering::SeqCst)
+                    );
+                }
+
                 pool.execute(move || {
-                    if let Err(e) = handle_connection(stream, verbose) {
+                    let _guard = ConnectionGuard(active_connections);
+                    if let Err(e) = handle_connection(stream, &config, &router) {
                         eprintln!("Error handling connection: {}", e);
                     }
                 });
@@ -169,63 +280,78 @@ pub fn start_server(
     }
 
     if config.verbose {
+        println!("Waiting for in-flight connections to drain...");
+    }
+
+    let drain_start = Instant::now();
+    while active_connections.load(Ordering::SeqCst) > 0 && drain_start.elapsed() < config.shutdown_timeout
+    {
+        thread::sleep(Duration::from_millis(10));
+    }
+
+    if config.verbose {
+        let remaining = active_connections.load(Ordering::SeqCst);
+        if remaining > 0 {
+            println!("Shutdown timeout reached with {remaining} connection(s) still active");
+        }
         println!("Server shutdown complete");
     }
 
     Ok(())
 }
 
-fn handle_connection(mut stream: TcpStream, verbose: bool) -> Result<(), Box<dyn Error>> {
-    let buf_reader = BufReader::new(&stream);
-    let request_line = buf_reader
-        .lines()
-        .next()
-        .ok_or_else(|| ServerError::new("Empty request"))??;
+fn handle_connection(
+    mut stream: TcpStream,
+    config: &ServerConfig,
+    router: &Router,
+) -> Result<(), Box<dyn Error>> {
+    stream.set_read_timeout(Some(config.read_timeout))?;
+    stream.set_write_timeout(Some(config.write_timeout))?;
+
+    let mut buf_reader = BufReader::new(&stream);
+    let request = match Request::parse(&mut buf_reader) {
+        Ok(request) => request,
+        Err(e) if e.kind() == ServerErrorKind::Timeout => {
+            let response = Response::new(408, "Request Timeout")
+                .with_header("Content-Type", "text/plain; charset=utf-8");
+            response.write_to(&mut stream).ok();
+            return Ok(());
+        }
+        Err(e) => return Err(Box::new(e)),
+    };
 
-    if verbose {
-        println!("Request: {}", request_line);
+    if config.verbose {
+        println!(
+            "Request: {} {} {}",
+            request.method, request.path, request.version
+        );
     }
 
-    let (status_line, filename) = match request_line.as_str() {
-        "GET / HTTP/1.1" => ("HTTP/1.1 200 OK", "hello.html"),
-        "GET /sleep HTTP/1.1" => {
-            thread::sleep(Duration::from_secs(5));
-            ("HTTP/1.1 200 OK", "hello.html")
+    let document_root = &config.document_root;
+    let response = if let Some(handler) = router.find(&request.method, &request.path) {
+        handler(&request)
+    } else if request.method == "GET" && request.path == "/sleep" {
+        thread::sleep(Duration::from_secs(5));
+        Response::ok(HELLO_HTML).with_header("Content-Type", "text/html; charset=utf-8")
+    } else if document_root.exists() {
+        match static_files::resolve(document_root, &request.path) {
+            Ok(file_path) => {
+                let contents = fs::read(&file_path)?;
+                Response::ok(contents)
+                    .with_header("Content-Type", static_files::content_type(&file_path))
+            }
+            Err(_) => Response::new(404, NOT_FOUND_HTML)
+                .with_header("Content-Type", "text/html; charset=utf-8"),
         }
-        _ => ("HTTP/1.1 404 NOT FOUND", "404.html"),
-    };
-
-    let contents = fs::read_to_string(filename).unwrap_or_else(|_| {
-        if filename == "hello.html" {
-            "<!DOCTYPE html>
-            <html>
-            <head>
-                <title>Hello</title>
-            </head>
-            <body>
-            <h1>Hello, world!</h1>
-            <p>Welcome to ThreadedHTTP server</p>
-            </body>
-            </html>".to_string()
-        } else {
-            "<!DOCTYPE html>
-            <html>
-            <head>
-                <title>404</title>
-            </head>
-            <body>
-            <h1>404 Not Found</h1>
-            <p>The requested resource was not found.</p>
-            </body>
-            </html>".to_string()
+    } else {
+        match request.path.as_str() {
+            "/" => Response::ok(HELLO_HTML).with_header("Content-Type", "text/html; charset=utf-8"),
+            _ => Response::new(404, NOT_FOUND_HTML)
+                .with_header("Content-Type", "text/html; charset=utf-8"),
         }
-    });
-
-    let length = contents.len();
-    let response = format!("{status_line}\r\nContent-Length: {length}\r\n\r\n{contents}");
+    };
 
-    stream.write_all(response.as_bytes())?;
-    stream.flush()?;
+    response.write_to(&mut stream)?;
 
     Ok(())
 }
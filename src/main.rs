@@ -1,8 +1,10 @@
 use clap::Parser;
+use std::path::PathBuf;
 use std::process;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use threaded_http::{start_server, ServerConfig};
+use std::time::Duration;
+use threaded_http::{start_server, Router, ServerConfig};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -18,6 +20,22 @@ struct Args {
 
     #[arg(short, long)]
     verbose: bool,
+
+    /// Directory to serve static files from.
+    #[arg(long, default_value = "public")]
+    root: PathBuf,
+
+    /// Maximum number of connections handled concurrently.
+    #[arg(long, default_value = "100")]
+    max_connections: usize,
+
+    /// Read timeout per connection, in seconds.
+    #[arg(long, default_value = "10")]
+    read_timeout: u64,
+
+    /// Write timeout per connection, in seconds.
+    #[arg(long, default_value = "10")]
+    write_timeout: u64,
 }
 
 fn main() {
@@ -27,6 +45,11 @@ fn main() {
         address: format!("{}:{}", args.host, args.port),
         pool_size: args.threads,
         verbose: args.verbose,
+        document_root: args.root,
+        max_connections: args.max_connections,
+        read_timeout: Duration::from_secs(args.read_timeout),
+        write_timeout: Duration::from_secs(args.write_timeout),
+        ..ServerConfig::default()
     };
 
     let shutdown = Arc::new(AtomicBool::new(false));
@@ -44,7 +67,9 @@ fn main() {
     println!("Thread pool size: {}", config.pool_size);
     println!("Press Ctrl+C to stop\n");
 
-    if let Err(e) = start_server(config, shutdown) {
+    let router = Router::new();
+
+    if let Err(e) = start_server(config, shutdown, router) {
         eprintln!("Server error: {}", e);
         process::exit(1);
     }
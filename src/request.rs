@@ -0,0 +1,227 @@
+use crate::ServerError;
+use std::collections::HashMap;
+use std::io::{self, BufRead, Read};
+
+/// Maximum number of header lines accepted in a single request.
+const MAX_HEADERS: usize = 64;
+/// Maximum bytes accepted for the request line alone, enforced while
+/// reading so an unterminated line can't grow memory without bound.
+const MAX_REQUEST_LINE_BYTES: usize = 8 * 1024;
+/// Maximum bytes accepted for a single header line, enforced the same way.
+const MAX_HEADER_LINE_BYTES: usize = 8 * 1024;
+/// Maximum total bytes read while parsing the request line and headers,
+/// to keep a slow or malicious client from growing memory unbounded.
+const MAX_HEADER_BYTES: usize = 8 * 1024;
+/// Maximum request body size accepted, regardless of what `Content-Length`
+/// claims, so a single request can't force a multi-gigabyte allocation.
+const MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Maps an I/O failure to a `ServerError`, preserving whether it was a
+/// timeout so callers can respond with `408 Request Timeout`.
+fn io_error(context: &str, e: io::Error) -> ServerError {
+    if matches!(
+        e.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    ) {
+        ServerError::timeout(&format!("{context}: {e}"))
+    } else {
+        ServerError::new(&format!("{context}: {e}"))
+    }
+}
+
+/// Reads a single line from `reader`, bounded to `limit` bytes so a line
+/// with no terminating `\n` can't be read into memory without bound.
+fn read_line_capped(
+    reader: &mut impl BufRead,
+    limit: usize,
+    context: &str,
+) -> Result<String, ServerError> {
+    let mut buf = Vec::new();
+    reader
+        .take(limit as u64)
+        .read_until(b'\n', &mut buf)
+        .map_err(|e| io_error(context, e))?;
+
+    if buf.len() as u64 >= limit as u64 && !buf.ends_with(b"\n") {
+        return Err(ServerError::new(&format!(
+            "{context}: line exceeds {limit} byte limit"
+        )));
+    }
+
+    String::from_utf8(buf).map_err(|_| ServerError::new(&format!("{context}: invalid utf-8")))
+}
+
+/// A parsed HTTP request: `Method Request-URI HTTP-Version CRLF`, followed
+/// by header lines and an optional message body.
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub version: String,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// Parses a request from `reader`, reading the request line, then
+    /// header lines until a blank line, then the body if `Content-Length`
+    /// is present.
+    pub fn parse(reader: &mut impl BufRead) -> Result<Request, ServerError> {
+        let request_line = read_line_capped(
+            reader,
+            MAX_REQUEST_LINE_BYTES,
+            "failed to read request line",
+        )?;
+
+        if request_line.is_empty() {
+            return Err(ServerError::new("empty request"));
+        }
+
+        let request_line = request_line.trim_end_matches(['\r', '\n']);
+        let mut parts = request_line.split_whitespace();
+        let method = parts
+            .next()
+            .ok_or_else(|| ServerError::new("malformed request line"))?;
+        let path = parts
+            .next()
+            .ok_or_else(|| ServerError::new("malformed request line"))?;
+        let version = parts
+            .next()
+            .ok_or_else(|| ServerError::new("malformed request line"))?;
+
+        if parts.next().is_some() {
+            return Err(ServerError::new("malformed request line"));
+        }
+
+        let mut headers = HashMap::new();
+        let mut header_lines = 0;
+        let mut total_bytes = request_line.len();
+
+        loop {
+            if headers.len() >= MAX_HEADERS {
+                return Err(ServerError::new("too many headers"));
+            }
+
+            let line =
+                read_line_capped(reader, MAX_HEADER_LINE_BYTES, "failed to read header line")?;
+
+            total_bytes += line.len();
+            if total_bytes > MAX_HEADER_BYTES {
+                return Err(ServerError::new("request headers too large"));
+            }
+
+            let line = line.trim_end_matches(['\r', '\n']);
+            if line.is_empty() {
+                break;
+            }
+
+            header_lines += 1;
+            if header_lines > MAX_HEADERS {
+                return Err(ServerError::new("too many headers"));
+            }
+
+            let (name, value) = line
+                .split_once(':')
+                .ok_or_else(|| ServerError::new("malformed header line"))?;
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+
+        let body = if let Some(len) = headers.get("content-length") {
+            let len: usize = len
+                .parse()
+                .map_err(|_| ServerError::new("invalid Content-Length"))?;
+
+            if len > MAX_BODY_BYTES {
+                return Err(ServerError::new("request body exceeds maximum size"));
+            }
+
+            let mut body = vec![0u8; len];
+            reader
+                .read_exact(&mut body)
+                .map_err(|e| io_error("failed to read body", e))?;
+            body
+        } else {
+            Vec::new()
+        };
+
+        Ok(Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            version: version.to_string(),
+            headers,
+            body,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ServerErrorKind;
+    use std::io::Cursor;
+
+    #[test]
+    fn parses_simple_get() {
+        let mut reader = Cursor::new(b"GET /foo HTTP/1.1\r\nHost: example.com\r\n\r\n".to_vec());
+        let request = Request::parse(&mut reader).unwrap();
+        assert_eq!(request.method, "GET");
+        assert_eq!(request.path, "/foo");
+        assert_eq!(request.version, "HTTP/1.1");
+        assert_eq!(
+            request.headers.get("host"),
+            Some(&"example.com".to_string())
+        );
+        assert!(request.body.is_empty());
+    }
+
+    #[test]
+    fn parses_body_with_content_length() {
+        let mut reader =
+            Cursor::new(b"POST /submit HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello".to_vec());
+        let request = Request::parse(&mut reader).unwrap();
+        assert_eq!(request.body, b"hello");
+    }
+
+    #[test]
+    fn rejects_malformed_request_line() {
+        let mut reader = Cursor::new(b"GET /foo\r\n\r\n".to_vec());
+        assert!(Request::parse(&mut reader).is_err());
+    }
+
+    #[test]
+    fn rejects_too_many_headers() {
+        let mut raw = String::from("GET / HTTP/1.1\r\n");
+        for i in 0..(MAX_HEADERS + 1) {
+            raw.push_str(&format!("X-Header-{i}: value\r\n"));
+        }
+        raw.push_str("\r\n");
+        let mut reader = Cursor::new(raw.into_bytes());
+        assert!(Request::parse(&mut reader).is_err());
+    }
+
+    #[test]
+    fn rejects_unterminated_request_line_over_limit() {
+        let raw = vec![b'a'; MAX_REQUEST_LINE_BYTES + 1];
+        let mut reader = Cursor::new(raw);
+        assert!(Request::parse(&mut reader).is_err());
+    }
+
+    #[test]
+    fn rejects_content_length_over_max_body_size() {
+        let raw = format!(
+            "POST /submit HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        let mut reader = Cursor::new(raw.into_bytes());
+        assert!(Request::parse(&mut reader).is_err());
+    }
+
+    #[test]
+    fn maps_blocking_io_error_to_timeout_kind() {
+        let err = io_error(
+            "failed to read request line",
+            io::Error::from(io::ErrorKind::WouldBlock),
+        );
+        assert_eq!(err.kind(), ServerErrorKind::Timeout);
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+/// An HTTP response: a status code, headers, and a body, with its own
+/// wire serialization so callers never hand-assemble a status line.
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+}
+
+impl Response {
+    pub fn new(status: u16, body: impl AsRef<[u8]>) -> Response {
+        Response {
+            status,
+            headers: HashMap::new(),
+            body: body.as_ref().to_vec(),
+        }
+    }
+
+    pub fn ok(body: impl AsRef<[u8]>) -> Response {
+        Response::new(200, body)
+    }
+
+    pub fn not_found() -> Response {
+        Response::new(404, "Not Found").with_header("Content-Type", "text/plain; charset=utf-8")
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Response {
+        self.headers.insert(name.to_string(), value.to_string());
+        self
+    }
+
+    pub fn with_status(mut self, status: u16) -> Response {
+        self.status = status;
+        self
+    }
+
+    /// Serializes the status line, headers, and body to `stream`.
+    pub fn write_to(&self, stream: &mut impl Write) -> io::Result<()> {
+        let mut head = format!(
+            "HTTP/1.1 {} {}\r\n",
+            self.status,
+            status_text(self.status)
+        );
+
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+
+        if !self
+            .headers
+            .keys()
+            .any(|name| name.eq_ignore_ascii_case("content-length"))
+        {
+            head.push_str(&format!("Content-Length: {}\r\n", self.body.len()));
+        }
+
+        head.push_str("\r\n");
+
+        stream.write_all(head.as_bytes())?;
+        stream.write_all(&self.body)?;
+        stream.flush()
+    }
+}
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        408 => "Request Timeout",
+        500 => "Internal Server Error",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_status_line_and_body() {
+        let response = Response::ok("hello");
+        let mut buf = Vec::new();
+        response.write_to(&mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        assert!(text.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(text.contains("Content-Length: 5\r\n"));
+        assert!(text.ends_with("hello"));
+    }
+
+    #[test]
+    fn not_found_uses_404_status() {
+        let mut buf = Vec::new();
+        Response::not_found().write_to(&mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        assert!(text.starts_with("HTTP/1.1 404 Not Found\r\n"));
+    }
+}
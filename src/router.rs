@@ -0,0 +1,59 @@
+use crate::{Request, Response};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A route handler: given a parsed request, produces a response.
+pub type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// A table of `(method, path)` pairs mapped to handlers, built by the
+/// caller before calling `start_server`.
+#[derive(Clone, Default)]
+pub struct Router {
+    routes: HashMap<(String, String), Arc<Handler>>,
+}
+
+impl Router {
+    pub fn new() -> Router {
+        Router {
+            routes: HashMap::new(),
+        }
+    }
+
+    /// Registers `handler` for `method` and `path`, e.g.
+    /// `Router::new().route("GET", "/", handler)`.
+    pub fn route<F>(mut self, method: &str, path: &str, handler: F) -> Router
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        self.routes.insert(
+            (method.to_ascii_uppercase(), path.to_string()),
+            Arc::new(handler),
+        );
+        self
+    }
+
+    pub(crate) fn find(&self, method: &str, path: &str) -> Option<Arc<Handler>> {
+        self.routes
+            .get(&(method.to_ascii_uppercase(), path.to_string()))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_registered_route() {
+        let router = Router::new().route("GET", "/hello", |_req| Response::ok("hi"));
+        assert!(router.find("GET", "/hello").is_some());
+        assert!(router.find("POST", "/hello").is_none());
+        assert!(router.find("GET", "/missing").is_none());
+    }
+
+    #[test]
+    fn method_lookup_is_case_insensitive() {
+        let router = Router::new().route("get", "/", |_req| Response::ok("hi"));
+        assert!(router.find("GET", "/").is_some());
+    }
+}
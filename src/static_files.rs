@@ -0,0 +1,117 @@
+use crate::ServerError;
+use std::path::{Component, Path, PathBuf};
+
+/// Resolves a request path to a file under `root`, rejecting anything that
+/// would escape it (`..` traversal, absolute paths, or symlink escape).
+///
+/// `/` maps to `index.html`. The returned path is canonicalized, so callers
+/// can assume it actually exists on disk.
+pub fn resolve(root: &Path, request_path: &str) -> Result<PathBuf, ServerError> {
+    let root = root
+        .canonicalize()
+        .map_err(|e| ServerError::new(&format!("invalid document root: {e}")))?;
+
+    let relative = if request_path == "/" {
+        "index.html"
+    } else {
+        request_path.trim_start_matches('/')
+    };
+
+    let mut target = root.clone();
+    for component in Path::new(relative).components() {
+        match component {
+            Component::Normal(part) => target.push(part),
+            Component::CurDir => {}
+            _ => return Err(ServerError::new("invalid request path")),
+        }
+    }
+
+    let target = target
+        .canonicalize()
+        .map_err(|_| ServerError::new("file not found"))?;
+
+    if !target.starts_with(&root) {
+        return Err(ServerError::new("request path escapes document root"));
+    }
+
+    if !target.is_file() {
+        return Err(ServerError::new("file not found"));
+    }
+
+    Ok(target)
+}
+
+/// Infers a `Content-Type` value from a file extension, defaulting to
+/// `application/octet-stream` for anything unrecognized.
+pub fn content_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("html") | Some("htm") => "text/html; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("js") => "application/javascript; charset=utf-8",
+        Some("json") => "application/json",
+        Some("png") => "image/png",
+        Some("jpg") | Some("jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("svg") => "image/svg+xml",
+        Some("ico") => "image/x-icon",
+        Some("txt") => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn make_root() -> PathBuf {
+        use std::sync::atomic::{AtomicU32, Ordering};
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+        let dir = std::env::temp_dir().join(format!(
+            "threaded_http_test_{}_{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("index.html"), "<h1>home</h1>").unwrap();
+        fs::create_dir_all(dir.join("sub")).unwrap();
+        fs::write(dir.join("sub").join("page.html"), "<h1>sub</h1>").unwrap();
+        dir
+    }
+
+    #[test]
+    fn resolves_index_for_root() {
+        let root = make_root();
+        let resolved = resolve(&root, "/").unwrap();
+        assert_eq!(resolved, root.canonicalize().unwrap().join("index.html"));
+    }
+
+    #[test]
+    fn resolves_nested_path() {
+        let root = make_root();
+        let resolved = resolve(&root, "/sub/page.html").unwrap();
+        assert_eq!(
+            resolved,
+            root.canonicalize().unwrap().join("sub").join("page.html")
+        );
+    }
+
+    #[test]
+    fn rejects_traversal_outside_root() {
+        let root = make_root();
+        assert!(resolve(&root, "/../../etc/passwd").is_err());
+    }
+
+    #[test]
+    fn rejects_missing_file() {
+        let root = make_root();
+        assert!(resolve(&root, "/missing.html").is_err());
+    }
+
+    #[test]
+    fn infers_content_type_from_extension() {
+        assert_eq!(content_type(Path::new("style.css")), "text/css; charset=utf-8");
+        assert_eq!(content_type(Path::new("archive.bin")), "application/octet-stream");
+    }
+}